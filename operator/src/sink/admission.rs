@@ -0,0 +1,382 @@
+//! Validating/mutating admission webhook for [`SinkWebhook`] resources.
+//!
+//! Malformed sinks should be rejected at `kubectl apply` time rather than
+//! silently producing a crash-looping pod. This subsystem:
+//!
+//! * generates and manages the supporting Kubernetes objects — a [`Service`]
+//!   pointing at the operator, a TLS [`Secret`] (self-signed CA + serving
+//!   certificate) mounted by the operator, and the
+//!   [`ValidatingWebhookConfiguration`]/[`MutatingWebhookConfiguration`] whose
+//!   `caBundle` is populated from the generated CA; and
+//! * exposes an HTTPS handler that deserializes `AdmissionReview` requests, runs
+//!   validation checks and returns an allow/deny response, defaulting omitted
+//!   fields through a JSON patch.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::State, routing::post, Json, Router};
+use k8s_openapi::api::{
+    admissionregistration::v1::{
+        MutatingWebhook, MutatingWebhookConfiguration, RuleWithOperations, ServiceReference,
+        ValidatingWebhook, ValidatingWebhookConfiguration, WebhookClientConfig,
+    },
+    core::v1::{Secret, Service, ServicePort, ServiceSpec},
+};
+use k8s_openapi::apimachinery::pkg::{
+    apis::meta::v1::ObjectMeta, util::intstr::IntOrString, ByteString,
+};
+use kube::{
+    api::{Patch, PatchParams},
+    core::admission::{AdmissionRequest, AdmissionResponse, AdmissionReview},
+    Api, Client,
+};
+use serde_json::json;
+use tracing::{info, warn};
+use url::Url;
+
+use super::webhook::SinkWebhook;
+use crate::reconcile::{Context, Error};
+
+/// Name shared by the generated Service, Secret and webhook configurations.
+static ADMISSION_NAME: &str = "sinkwebhook-admission";
+/// Path the API server posts `AdmissionReview`s to.
+static ADMISSION_PATH: &str = "/admission/sinkwebhook";
+/// Port the operator serves the admission handler on.
+static ADMISSION_PORT: i32 = 8443;
+
+/// A self-signed CA and the serving certificate it signs.
+struct GeneratedCerts {
+    ca_pem: String,
+    cert_pem: String,
+    key_pem: String,
+}
+
+/// Generate a self-signed CA and a serving certificate valid for the
+/// in-cluster DNS name of the admission Service.
+fn generate_certs(namespace: &str) -> Result<GeneratedCerts, Error> {
+    use rcgen::{Certificate, CertificateParams, DnType, IsCa, KeyUsagePurpose};
+
+    let dns_name = format!("{ADMISSION_NAME}.{namespace}.svc");
+
+    let mut ca_params = CertificateParams::default();
+    ca_params.is_ca = IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+    ca_params.key_usages = vec![KeyUsagePurpose::KeyCertSign, KeyUsagePurpose::CrlSign];
+    ca_params
+        .distinguished_name
+        .push(DnType::CommonName, "sinkwebhook-admission-ca");
+    let ca = Certificate::from_params(ca_params).map_err(|err| Error::Admission(err.to_string()))?;
+
+    let mut cert_params = CertificateParams::new(vec![dns_name.clone()]);
+    cert_params
+        .distinguished_name
+        .push(DnType::CommonName, dns_name);
+    let cert =
+        Certificate::from_params(cert_params).map_err(|err| Error::Admission(err.to_string()))?;
+
+    let ca_pem = ca
+        .serialize_pem()
+        .map_err(|err| Error::Admission(err.to_string()))?;
+    let cert_pem = cert
+        .serialize_pem_with_signer(&ca)
+        .map_err(|err| Error::Admission(err.to_string()))?;
+    let key_pem = cert.serialize_private_key_pem();
+
+    Ok(GeneratedCerts {
+        ca_pem,
+        cert_pem,
+        key_pem,
+    })
+}
+
+/// Build the TLS Secret mounted by the operator to serve HTTPS.
+fn tls_secret(namespace: &str, certs: &GeneratedCerts) -> Secret {
+    let mut data = std::collections::BTreeMap::new();
+    data.insert(
+        "tls.crt".to_string(),
+        ByteString(certs.cert_pem.clone().into_bytes()),
+    );
+    data.insert(
+        "tls.key".to_string(),
+        ByteString(certs.key_pem.clone().into_bytes()),
+    );
+    data.insert(
+        "ca.crt".to_string(),
+        ByteString(certs.ca_pem.clone().into_bytes()),
+    );
+
+    Secret {
+        metadata: ObjectMeta {
+            name: Some(ADMISSION_NAME.to_string()),
+            namespace: Some(namespace.to_string()),
+            ..ObjectMeta::default()
+        },
+        type_: Some("kubernetes.io/tls".to_string()),
+        data: Some(data),
+        ..Secret::default()
+    }
+}
+
+/// Build the Service that routes API-server admission traffic to the operator.
+fn admission_service(namespace: &str) -> Service {
+    Service {
+        metadata: ObjectMeta {
+            name: Some(ADMISSION_NAME.to_string()),
+            namespace: Some(namespace.to_string()),
+            ..ObjectMeta::default()
+        },
+        spec: Some(ServiceSpec {
+            selector: Some(std::collections::BTreeMap::from([(
+                "app".to_string(),
+                "operator".to_string(),
+            )])),
+            ports: Some(vec![ServicePort {
+                port: 443,
+                target_port: Some(IntOrString::Int(ADMISSION_PORT)),
+                ..ServicePort::default()
+            }]),
+            ..ServiceSpec::default()
+        }),
+        ..Service::default()
+    }
+}
+
+fn client_config(namespace: &str, ca_bundle: &str, path: &str) -> WebhookClientConfig {
+    WebhookClientConfig {
+        service: Some(ServiceReference {
+            name: ADMISSION_NAME.to_string(),
+            namespace: namespace.to_string(),
+            path: Some(path.to_string()),
+            port: Some(443),
+        }),
+        ca_bundle: Some(ByteString(ca_bundle.as_bytes().to_vec())),
+        url: None,
+    }
+}
+
+fn webhook_rules() -> Vec<RuleWithOperations> {
+    vec![RuleWithOperations {
+        api_groups: Some(vec!["apibara.com".to_string()]),
+        api_versions: Some(vec!["v1alpha2".to_string()]),
+        operations: Some(vec!["CREATE".to_string(), "UPDATE".to_string()]),
+        resources: Some(vec!["sinkwebhooks".to_string()]),
+        scope: Some("Namespaced".to_string()),
+    }]
+}
+
+fn validating_configuration(namespace: &str, ca_bundle: &str) -> ValidatingWebhookConfiguration {
+    ValidatingWebhookConfiguration {
+        metadata: ObjectMeta {
+            name: Some(ADMISSION_NAME.to_string()),
+            ..ObjectMeta::default()
+        },
+        webhooks: Some(vec![ValidatingWebhook {
+            name: "validate.sinkwebhook.apibara.com".to_string(),
+            admission_review_versions: vec!["v1".to_string()],
+            side_effects: "None".to_string(),
+            client_config: client_config(namespace, ca_bundle, ADMISSION_PATH),
+            rules: Some(webhook_rules()),
+            ..ValidatingWebhook::default()
+        }]),
+    }
+}
+
+fn mutating_configuration(namespace: &str, ca_bundle: &str) -> MutatingWebhookConfiguration {
+    MutatingWebhookConfiguration {
+        metadata: ObjectMeta {
+            name: Some(ADMISSION_NAME.to_string()),
+            ..ObjectMeta::default()
+        },
+        webhooks: Some(vec![MutatingWebhook {
+            name: "mutate.sinkwebhook.apibara.com".to_string(),
+            admission_review_versions: vec!["v1".to_string()],
+            side_effects: "None".to_string(),
+            client_config: client_config(namespace, ca_bundle, &format!("{ADMISSION_PATH}/mutate")),
+            rules: Some(webhook_rules()),
+            ..MutatingWebhook::default()
+        }]),
+    }
+}
+
+/// Read the persisted serving certs out of the TLS Secret, if it already
+/// exists. Returns `None` when the Secret is absent or missing any key so the
+/// caller falls back to generating a fresh CA.
+async fn existing_certs(client: &Client, namespace: &str) -> Option<GeneratedCerts> {
+    let secrets: Api<Secret> = Api::namespaced(client.clone(), namespace);
+    let secret = secrets.get_opt(ADMISSION_NAME).await.ok().flatten()?;
+    let data = secret.data?;
+
+    let read = |key: &str| -> Option<String> {
+        data.get(key)
+            .and_then(|b| String::from_utf8(b.0.clone()).ok())
+    };
+
+    Some(GeneratedCerts {
+        cert_pem: read("tls.crt")?,
+        key_pem: read("tls.key")?,
+        ca_pem: read("ca.crt")?,
+    })
+}
+
+/// Reconcile the supporting admission resources and return the serving certs so
+/// the HTTPS server can be configured with the matching key pair.
+///
+/// The CA is generated once and persisted in the TLS Secret: a restart (or an
+/// additional replica) reuses the existing certs instead of minting a new CA,
+/// which would otherwise rewrite every `caBundle` and break TLS against pods
+/// still serving the previous cert.
+async fn ensure_resources(client: &Client, namespace: &str) -> Result<GeneratedCerts, Error> {
+    let certs = match existing_certs(client, namespace).await {
+        Some(certs) => certs,
+        None => generate_certs(namespace)?,
+    };
+    let params = PatchParams::apply(ADMISSION_NAME);
+
+    let secrets: Api<Secret> = Api::namespaced(client.clone(), namespace);
+    secrets
+        .patch(
+            ADMISSION_NAME,
+            &params,
+            &Patch::Apply(tls_secret(namespace, &certs)),
+        )
+        .await?;
+
+    let services: Api<Service> = Api::namespaced(client.clone(), namespace);
+    services
+        .patch(
+            ADMISSION_NAME,
+            &params,
+            &Patch::Apply(admission_service(namespace)),
+        )
+        .await?;
+
+    let validating: Api<ValidatingWebhookConfiguration> = Api::all(client.clone());
+    validating
+        .patch(
+            ADMISSION_NAME,
+            &params,
+            &Patch::Apply(validating_configuration(namespace, &certs.ca_pem)),
+        )
+        .await?;
+
+    let mutating: Api<MutatingWebhookConfiguration> = Api::all(client.clone());
+    mutating
+        .patch(
+            ADMISSION_NAME,
+            &params,
+            &Patch::Apply(mutating_configuration(namespace, &certs.ca_pem)),
+        )
+        .await?;
+
+    Ok(certs)
+}
+
+/// Validate a sink spec, returning a human-readable reason on rejection.
+fn validate(webhook: &SinkWebhook) -> Result<(), String> {
+    let url = Url::parse(&webhook.spec.target_url)
+        .map_err(|err| format!("target_url is not a valid URL: {err}"))?;
+    if !url.has_host() || !(url.scheme() == "http" || url.scheme() == "https") {
+        return Err("target_url must be an absolute http(s) URL".to_string());
+    }
+
+    webhook
+        .spec
+        .common
+        .stream
+        .validate()
+        .map_err(|err| format!("invalid stream configuration: {err}"))?;
+
+    Ok(())
+}
+
+/// Build a JSON patch that fills in omitted defaults, or `None` if nothing
+/// needs defaulting.
+fn default_patch(webhook: &SinkWebhook) -> Option<json_patch::Patch> {
+    let mut ops = Vec::new();
+    if webhook.spec.raw.is_none() {
+        ops.push(json_patch::PatchOperation::Add(json_patch::AddOperation {
+            path: "/spec/raw".to_string(),
+            value: json!(false),
+        }));
+    }
+    if ops.is_empty() {
+        None
+    } else {
+        Some(json_patch::Patch(ops))
+    }
+}
+
+async fn handle_validate(
+    State(_ctx): State<Arc<Context>>,
+    Json(review): Json<AdmissionReview<SinkWebhook>>,
+) -> Json<AdmissionReview<SinkWebhook>> {
+    Json(respond(review, false))
+}
+
+async fn handle_mutate(
+    State(_ctx): State<Arc<Context>>,
+    Json(review): Json<AdmissionReview<SinkWebhook>>,
+) -> Json<AdmissionReview<SinkWebhook>> {
+    Json(respond(review, true))
+}
+
+/// Run validation (and, for the mutating path, defaulting) on a review.
+fn respond(
+    review: AdmissionReview<SinkWebhook>,
+    mutate: bool,
+) -> AdmissionReview<SinkWebhook> {
+    let req: AdmissionRequest<SinkWebhook> = match review.try_into() {
+        Ok(req) => req,
+        Err(err) => {
+            warn!(error = %err, "invalid admission review");
+            return AdmissionResponse::invalid(err.to_string()).into_review();
+        }
+    };
+
+    let mut res = AdmissionResponse::from(&req);
+    if let Some(webhook) = req.object.as_ref() {
+        if let Err(reason) = validate(webhook) {
+            return res.deny(reason).into_review();
+        }
+        if mutate {
+            if let Some(patch) = default_patch(webhook) {
+                res = match res.with_patch(patch) {
+                    Ok(res) => res,
+                    Err(err) => {
+                        return AdmissionResponse::invalid(err.to_string()).into_review()
+                    }
+                };
+            }
+        }
+    }
+
+    res.into_review()
+}
+
+/// Start the admission webhook subsystem: reconcile its supporting resources
+/// and serve the HTTPS handler.
+pub async fn start_admission(ctx: Context) -> Result<(), Error> {
+    let namespace = ctx.configuration.namespace.clone();
+    let certs = ensure_resources(&ctx.client, &namespace).await?;
+
+    let config = axum_server::tls_rustls::RustlsConfig::from_pem(
+        certs.cert_pem.into_bytes(),
+        certs.key_pem.into_bytes(),
+    )
+    .await
+    .map_err(|err| Error::Admission(err.to_string()))?;
+
+    let app = Router::new()
+        .route(ADMISSION_PATH, post(handle_validate))
+        .route(&format!("{ADMISSION_PATH}/mutate"), post(handle_mutate))
+        .with_state(Arc::new(ctx));
+
+    let addr = SocketAddr::from(([0, 0, 0, 0], ADMISSION_PORT as u16));
+    info!(%addr, "starting admission webhook server");
+
+    axum_server::bind_rustls(addr, config)
+        .serve(app.into_make_service())
+        .await
+        .map_err(|err| Error::Admission(err.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+//! Custom HTTP headers forwarded by the webhook sink on every POST.
+//!
+//! Header values may be given inline, or sourced from a key of a `Secret` or
+//! `ConfigMap` for sensitive values such as `Authorization` tokens. Secret- and
+//! config-map-backed values are injected as `EnvVar`s with a `value_from`
+//! reference so credentials never appear in the pod manifest or status.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::{
+    ConfigMapKeySelector, EnvVar, EnvVarSource, SecretKeySelector,
+};
+use serde::{Deserialize, Serialize};
+
+/// Reference to a single key of a `Secret` or `ConfigMap`.
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRef {
+    /// Name of the `Secret`/`ConfigMap`.
+    pub name: String,
+    /// Key within the object holding the header value.
+    pub key: String,
+}
+
+/// The value of a forwarded header.
+#[derive(Clone, Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "camelCase", untagged)]
+pub enum HeaderValue {
+    /// An inline, plaintext value.
+    Inline(String),
+    /// A value read from a `Secret` key.
+    Secret { secret: KeyRef },
+    /// A value read from a `ConfigMap` key.
+    ConfigMap { config_map: KeyRef },
+}
+
+/// Environment variable name carrying a given header's value.
+fn header_env_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("HEADER_{}", sanitized.to_ascii_uppercase())
+}
+
+/// Serialize the configured headers into container environment variables.
+///
+/// Each header is exposed as a `HEADER_<NAME>` variable, and a JSON-encoded
+/// `HEADERS` variable maps the original header name to its environment variable
+/// so the sink can reconstruct and forward them.
+pub fn headers_to_env(headers: &BTreeMap<String, HeaderValue>) -> Vec<EnvVar> {
+    let mut env = Vec::with_capacity(headers.len() + 1);
+    let mut mapping = BTreeMap::new();
+
+    for (name, value) in headers {
+        let env_name = header_env_name(name);
+        mapping.insert(name.clone(), env_name.clone());
+
+        let var = match value {
+            HeaderValue::Inline(value) => EnvVar {
+                name: env_name,
+                value: Some(value.clone()),
+                ..EnvVar::default()
+            },
+            HeaderValue::Secret { secret } => EnvVar {
+                name: env_name,
+                value_from: Some(EnvVarSource {
+                    secret_key_ref: Some(SecretKeySelector {
+                        name: Some(secret.name.clone()),
+                        key: secret.key.clone(),
+                        optional: None,
+                    }),
+                    ..EnvVarSource::default()
+                }),
+                ..EnvVar::default()
+            },
+            HeaderValue::ConfigMap { config_map } => EnvVar {
+                name: env_name,
+                value_from: Some(EnvVarSource {
+                    config_map_key_ref: Some(ConfigMapKeySelector {
+                        name: Some(config_map.name.clone()),
+                        key: config_map.key.clone(),
+                        optional: None,
+                    }),
+                    ..EnvVarSource::default()
+                }),
+                ..EnvVar::default()
+            },
+        };
+        env.push(var);
+    }
+
+    if let Ok(encoded) = serde_json::to_string(&mapping) {
+        env.push(EnvVar {
+            name: "HEADERS".to_string(),
+            value: Some(encoded),
+            ..EnvVar::default()
+        });
+    }
+
+    env
+}
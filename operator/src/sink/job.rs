@@ -0,0 +1,105 @@
+//! Helpers for running a sink as a Kubernetes `batch/v1` Job.
+//!
+//! This mirrors the bare-`Pod` execution path: the controller builds the same
+//! container spec and wraps it here, letting Kubernetes handle crash retries
+//! (`backoffLimit`), run deadlines (`activeDeadlineSeconds`) and post-completion
+//! cleanup (`ttlSecondsAfterFinished`) natively instead of the controller
+//! deleting and recreating pods by hand. It is written to be reusable by the
+//! webhook sink and any future sink.
+
+use k8s_openapi::api::{
+    batch::v1::{Job, JobSpec},
+    core::v1::{PodSpec, PodTemplateSpec},
+};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use serde::{Deserialize, Serialize};
+
+/// Selects how a sink resource is executed.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "PascalCase")]
+pub enum ExecutionBackend {
+    /// Run the sink as a single bare `Pod` managed directly by the controller.
+    #[default]
+    Pod,
+    /// Run the sink as a `batch/v1` Job and let Kubernetes manage retries.
+    Job,
+}
+
+/// Knobs forwarded to the generated `Job` spec.
+#[derive(Clone, Debug)]
+pub struct JobSettings {
+    /// Number of retries Kubernetes performs before marking the Job failed.
+    pub backoff_limit: i32,
+    /// Wall-clock deadline for the Job, after which it is terminated.
+    pub active_deadline_seconds: Option<i64>,
+    /// How long to keep a finished Job (and its pods) before garbage collection.
+    pub ttl_seconds_after_finished: Option<i32>,
+}
+
+impl Default for JobSettings {
+    fn default() -> Self {
+        Self {
+            backoff_limit: 4,
+            active_deadline_seconds: None,
+            ttl_seconds_after_finished: Some(300),
+        }
+    }
+}
+
+/// Wrap a pod spec in a `Job` manifest ready to be server-side applied.
+///
+/// The pod spec must use a `restart_policy` of `Never` or `OnFailure`, as
+/// required by the Job controller.
+pub fn job_manifest(metadata: ObjectMeta, pod_spec: PodSpec, settings: &JobSettings) -> Job {
+    Job {
+        metadata: metadata.clone(),
+        spec: Some(JobSpec {
+            backoff_limit: Some(settings.backoff_limit),
+            active_deadline_seconds: settings.active_deadline_seconds,
+            ttl_seconds_after_finished: settings.ttl_seconds_after_finished,
+            template: PodTemplateSpec {
+                metadata: Some(metadata),
+                spec: Some(pod_spec),
+            },
+            ..JobSpec::default()
+        }),
+        ..Job::default()
+    }
+}
+
+/// Phase derived from a Job's `.status.conditions`.
+pub enum JobPhase {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// Derive a sink phase from the Job's `.status.conditions` rather than by
+/// comparing the `.status.failed` counter to `backoffLimit`. Kubernetes sets a
+/// `Complete` or `Failed` condition (status `True`) once the Job reaches a
+/// terminal state; the `failed` counter does not reliably exceed `backoffLimit`
+/// when retries are exhausted, which would leave a given-up Job reported as
+/// `Running` forever.
+pub fn job_phase(job: &Job) -> JobPhase {
+    let conditions = job
+        .status
+        .as_ref()
+        .and_then(|s| s.conditions.as_ref());
+
+    let has_true_condition = |type_: &str| {
+        conditions
+            .map(|cs| {
+                cs.iter()
+                    .any(|c| c.type_ == type_ && c.status == "True")
+            })
+            .unwrap_or(false)
+    };
+
+    if has_true_condition("Complete") {
+        JobPhase::Succeeded
+    } else if has_true_condition("Failed") {
+        JobPhase::Failed
+    } else {
+        JobPhase::Running
+    }
+}
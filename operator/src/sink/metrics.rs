@@ -0,0 +1,128 @@
+//! Controller-level Prometheus metrics.
+//!
+//! Built entirely from data already computed during reconcile — reconcile
+//! count, restart increments and the current phase per sink — so operators have
+//! a scrape target for alerting on crash-looping sinks.
+
+use std::{net::SocketAddr, sync::Arc};
+
+use axum::{extract::State, routing::get, Router};
+use prometheus::{IntCounter, IntGaugeVec, Opts, Registry};
+use tracing::info;
+
+use crate::reconcile::{Context, Error};
+
+/// Metrics shared across reconciles via the [`Context`](crate::reconcile::Context).
+#[derive(Clone)]
+pub struct Metrics {
+    pub registry: Registry,
+    reconciles: IntCounter,
+    restarts: IntCounter,
+    phase: IntGaugeVec,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let registry = Registry::new();
+
+        let reconciles =
+            IntCounter::new("sink_reconciles_total", "Total number of sink reconciles")
+                .expect("valid metric");
+        let restarts = IntCounter::new(
+            "sink_restarts_total",
+            "Total number of sink pod restarts triggered by the controller",
+        )
+        .expect("valid metric");
+        let phase = IntGaugeVec::new(
+            Opts::new("sink_phase", "Current phase of a sink, 1 for the active phase"),
+            &["namespace", "name", "phase"],
+        )
+        .expect("valid metric");
+
+        registry
+            .register(Box::new(reconciles.clone()))
+            .expect("register reconciles");
+        registry
+            .register(Box::new(restarts.clone()))
+            .expect("register restarts");
+        registry
+            .register(Box::new(phase.clone()))
+            .expect("register phase");
+
+        Self {
+            registry,
+            reconciles,
+            restarts,
+            phase,
+        }
+    }
+
+    /// Count a reconcile pass.
+    pub fn record_reconcile(&self) {
+        self.reconciles.inc();
+    }
+
+    /// Count `increment` pod restarts triggered this reconcile.
+    pub fn record_restart(&self, increment: u32) {
+        self.restarts.inc_by(increment as u64);
+    }
+
+    /// Mark `phase` as the current phase of the given sink, clearing the others.
+    pub fn set_phase(&self, namespace: &str, name: &str, phase: &str) {
+        for candidate in ["Running", "Error", "Succeeded"] {
+            let value = if candidate == phase { 1 } else { 0 };
+            self.phase
+                .with_label_values(&[namespace, name, candidate])
+                .set(value);
+        }
+    }
+
+    /// Drop every phase series for a deleted sink so stale gauges stop firing
+    /// alerts after the resource is gone.
+    pub fn clear_phase(&self, namespace: &str, name: &str) {
+        for candidate in ["Running", "Error", "Succeeded"] {
+            let _ = self
+                .phase
+                .remove_label_values(&[namespace, name, candidate]);
+        }
+    }
+
+    /// Encode the current metrics in the Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        use prometheus::Encoder;
+
+        let mut buffer = Vec::new();
+        let encoder = prometheus::TextEncoder::new();
+        let families = self.registry.gather();
+        let _ = encoder.encode(&families, &mut buffer);
+        String::from_utf8(buffer).unwrap_or_default()
+    }
+}
+
+async fn serve_metrics(State(ctx): State<Arc<Context>>) -> String {
+    ctx.metrics.encode()
+}
+
+/// Serve the controller metrics on `/metrics` for Prometheus to scrape.
+pub async fn start_metrics(ctx: Arc<Context>, address: SocketAddr) -> Result<(), Error> {
+    let app = Router::new()
+        .route("/metrics", get(serve_metrics))
+        .with_state(ctx);
+
+    info!(%address, "starting metrics server");
+
+    let listener = tokio::net::TcpListener::bind(address)
+        .await
+        .map_err(|err| Error::Server(err.to_string()))?;
+    axum::serve(listener, app)
+        .await
+        .map_err(|err| Error::Server(err.to_string()))?;
+
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Watches `Node` objects and reschedules sink pods away from lost nodes.
+//!
+//! The webhook controller only reacts to its own `SinkWebhook` and owned `Pod`
+//! events, so a pod stranded on a `NotReady` or deleted node would otherwise sit
+//! idle until the next periodic reconcile. This subsystem streams nodes,
+//! detects the ones that become unreachable (missing `Ready` condition or a
+//! deletion timestamp) or disappear entirely, deletes the sink pods scheduled
+//! onto them and nudges the owning `SinkWebhook` so a replacement is scheduled
+//! elsewhere immediately.
+
+use std::sync::Arc;
+
+use futures::StreamExt;
+use k8s_openapi::api::core::v1::{Node, Pod};
+use kube::{
+    api::{DeleteParams, ListParams, Patch, PatchParams},
+    runtime::watcher::{self, Event},
+    Api, ResourceExt,
+};
+use serde_json::json;
+use tracing::{info, warn};
+
+use crate::reconcile::{Context, Error};
+
+/// Returns `true` when a node can no longer run pods: it is being deleted or it
+/// is missing a `True` `Ready` condition.
+fn is_unreachable(node: &Node) -> bool {
+    if node.meta().deletion_timestamp.is_some() {
+        return true;
+    }
+
+    let ready = node
+        .status
+        .as_ref()
+        .and_then(|status| status.conditions.as_ref())
+        .and_then(|conditions| conditions.iter().find(|c| c.type_ == "Ready"));
+
+    match ready {
+        Some(condition) => condition.status != "True",
+        None => true,
+    }
+}
+
+/// Evict pods off a lost node, logging and swallowing any API error so a single
+/// transient failure (list/delete conflict, throttling) never terminates the
+/// watcher subsystem.
+async fn evict_node(ctx: &Arc<Context>, node_name: &str) {
+    if let Err(err) = evict_pods_on_node(ctx, node_name).await {
+        warn!(error = ?err, node = %node_name, "failed to evict pods from lost node");
+    }
+}
+
+/// Delete every sink pod scheduled onto `node_name` and enqueue its owning
+/// `SinkWebhook` for immediate reconciliation.
+async fn evict_pods_on_node(ctx: &Arc<Context>, node_name: &str) -> Result<(), Error> {
+    let pods: Api<Pod> = Api::all(ctx.client.clone());
+    let params = ListParams::default().fields(&format!("spec.nodeName={node_name}"));
+
+    for pod in pods.list(&params).await?.into_iter() {
+        // Only touch sink pods, identified by the label the controller stamps on
+        // them; the label's value is the name of the owning `SinkWebhook`.
+        let owner = pod
+            .labels()
+            .get("apibara.com/sink")
+            .cloned();
+
+        let Some(owner) = owner else {
+            continue;
+        };
+
+        let Some(ns) = pod.namespace() else {
+            continue;
+        };
+
+        info!(
+            pod = %pod.name_any(),
+            node = %node_name,
+            "evicting sink pod from lost node",
+        );
+
+        let namespaced: Api<Pod> = Api::namespaced(ctx.client.clone(), &ns);
+        namespaced
+            .delete(&pod.name_any(), &DeleteParams::default())
+            .await?;
+
+        enqueue_owner(ctx, &ns, &owner, node_name).await?;
+    }
+
+    Ok(())
+}
+
+/// Force a reconcile of the owning sink by bumping a management annotation.
+///
+/// The annotation value carries the lost node's name and the eviction time so
+/// that repeated node failures always change `resourceVersion` and reliably
+/// generate a watch event; re-applying a constant value would be a silent no-op.
+async fn enqueue_owner(
+    ctx: &Arc<Context>,
+    namespace: &str,
+    name: &str,
+    node_name: &str,
+) -> Result<(), Error> {
+    use super::webhook::SinkWebhook;
+
+    let webhooks: Api<SinkWebhook> = Api::namespaced(ctx.client.clone(), namespace);
+    let value = format!(
+        "{node_name}@{}",
+        k8s_openapi::chrono::Utc::now().to_rfc3339()
+    );
+    let patch = json!({
+        "metadata": {
+            "annotations": {
+                "apibara.com/rescheduled": value
+            }
+        }
+    });
+
+    webhooks
+        .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+        .await?;
+
+    Ok(())
+}
+
+/// Stream `Node` objects and reschedule sink pods off any node that becomes
+/// unreachable or is removed from the cluster.
+///
+/// Runs until the watch stream ends; started as a second controller task from
+/// `start_controller`, sharing the same [`Context`].
+pub async fn start_node_watcher(ctx: Arc<Context>) -> Result<(), Error> {
+    let nodes: Api<Node> = Api::all(ctx.client.clone());
+
+    info!("starting node failure watcher");
+
+    let mut stream = watcher(nodes, watcher::Config::default()).boxed();
+    while let Some(event) = stream.next().await {
+        match event {
+            Ok(Event::Applied(node)) => {
+                if is_unreachable(&node) {
+                    evict_node(&ctx, &node.name_any()).await;
+                }
+            }
+            // The node is gone: garbage-collect any pods still pinned to it.
+            Ok(Event::Deleted(node)) => {
+                evict_node(&ctx, &node.name_any()).await;
+            }
+            Ok(Event::Restarted(nodes)) => {
+                for node in nodes.iter().filter(|node| is_unreachable(node)) {
+                    evict_node(&ctx, &node.name_any()).await;
+                }
+            }
+            Err(err) => warn!(error = ?err, "node watcher stream error"),
+        }
+    }
+
+    Ok(())
+}
@@ -19,17 +19,36 @@ use tracing::{error, info, instrument, warn};
 
 use super::{
     common::CommonStatus,
+    job::{self, ExecutionBackend, JobPhase, JobSettings},
     webhook::{SinkWebhook, SinkWebhookStatus},
 };
 use crate::reconcile::{Context, Error, ReconcileItem};
 
 static WEBHOOK_FINALIZER: &str = "sinkwebhook.apibara.com";
 
+/// Upper bound for the exponential restart backoff.
+static RESTART_BACKOFF_CAP: Duration = Duration::from_secs(10 * 60);
+
+/// Compute the restart delay for a crashed pod as `min(base * 2^restart_count, cap)`.
+fn restart_backoff(base: Duration, cap: Duration, restart_count: u32) -> Duration {
+    base.checked_mul(1u32.checked_shl(restart_count).unwrap_or(u32::MAX))
+        .unwrap_or(cap)
+        .min(cap)
+}
+
 impl SinkWebhook {
     #[instrument(skip_all)]
     async fn reconcile(&self, ctx: Arc<Context>) -> Result<Action, Error> {
         use api::core::v1::Pod;
 
+        ctx.metrics.record_reconcile();
+
+        // When configured, run the sink as a Job and let Kubernetes own the
+        // retry/cleanup lifecycle instead of managing a bare pod by hand.
+        if matches!(self.spec.common.backend, ExecutionBackend::Job) {
+            return self.reconcile_job(ctx).await;
+        }
+
         let ns = self.namespace().expect("webhook is namespaced");
         let name = self.name_any();
 
@@ -47,8 +66,17 @@ impl SinkWebhook {
             None
         };
 
+        let current_restart_count = self
+            .status
+            .as_ref()
+            .and_then(|status| status.common.restart_count)
+            .unwrap_or_default();
+
+        let timing = &ctx.configuration.timing;
+
         let mut restart_increment = 0;
         let mut error_condition = None;
+        let mut requeue_after = timing.requeue_interval;
         if let Some(existing_pod) = existing_pod {
             // The pod exists. Is it running?
             let container_status = existing_pod.status.as_ref().and_then(|status| {
@@ -58,33 +86,92 @@ impl SinkWebhook {
                     .and_then(|statuses| statuses.first())
             });
 
-            let container_finished_at = container_status
+            let terminated = container_status
                 .and_then(|cs| cs.state.as_ref())
-                .and_then(|st| st.terminated.clone())
-                .and_then(|ts| ts.finished_at);
-
-            // Delete pod so that the next section will recreate a new one.
-            // For now, delete once every minute.
-            // TODO: should depend on the exit code.
-            if let Some(finished_at) = container_finished_at {
-                let elapsed = (Utc::now().time() - finished_at.0.time())
-                    .to_std()
-                    .unwrap_or_default();
-
-                if elapsed > Duration::from_secs(60) {
-                    info!(pod = %existing_pod.name_any(), "deleting pod");
+                .and_then(|st| st.terminated.clone());
+
+            // The container reached a terminal state. Use its exit code to decide
+            // whether the sink completed cleanly or crashed and must be restarted.
+            if let Some(terminated) = terminated {
+                let finished_at = terminated
+                    .finished_at
+                    .clone()
+                    .unwrap_or(meta::v1::Time(Utc::now()));
+
+                if terminated.exit_code == 0 {
+                    // Clean completion: mark the sink as succeeded and stop recreating
+                    // the pod on every reconcile.
+                    info!(pod = %existing_pod.name_any(), "sink completed successfully");
+
+                    let completed_condition = Condition {
+                        last_transition_time: finished_at,
+                        type_: "Completed".to_string(),
+                        message: "Sink completed successfully".to_string(),
+                        observed_generation: self.meta().generation,
+                        reason: "PodSucceeded".to_string(),
+                        status: "True".to_string(),
+                    };
+
+                    let status = json!({
+                        "status": SinkWebhookStatus {
+                            common: CommonStatus {
+                                pod_created: existing_pod.meta().creation_timestamp.clone(),
+                                instance_name: existing_pod.meta().name.clone(),
+                                phase: Some("Succeeded".to_string()),
+                                conditions: Some(vec![completed_condition]),
+                                restart_count: Some(current_restart_count),
+                            }
+                        }
+                    });
+
+                    webhooks
+                        .patch_status(&name, &PatchParams::default(), &Patch::Merge(&status))
+                        .await?;
+
+                    ctx.metrics.set_phase(&ns, &name, "Succeeded");
+                    // The sink is done; stop recreating and only wake on change
+                    // instead of busy-looping on the requeue interval.
+                    return Ok(Action::await_change());
+                }
+
+                // Non-zero exit code: the sink crashed. Back off exponentially before
+                // recreating the pod, based on how many times it has already restarted.
+                let backoff = restart_backoff(
+                    timing.restart_threshold,
+                    RESTART_BACKOFF_CAP,
+                    current_restart_count as u32,
+                );
+                let elapsed = (Utc::now() - finished_at.0).to_std().unwrap_or_default();
+
+                if elapsed > backoff {
+                    info!(
+                        pod = %existing_pod.name_any(),
+                        exit_code = terminated.exit_code,
+                        "deleting crashed pod",
+                    );
                     pods.delete(&existing_pod.name_any(), &DeleteParams::default())
                         .await?;
                     restart_increment = 1;
                 } else {
+                    let remaining = backoff - elapsed;
+                    let backoff_delta = k8s_openapi::chrono::Duration::from_std(backoff).unwrap_or_else(|_| {
+                        k8s_openapi::chrono::Duration::seconds(RESTART_BACKOFF_CAP.as_secs() as i64)
+                    });
+                    let next_retry = finished_at.0 + backoff_delta;
                     error_condition = Some(Condition {
                         last_transition_time: finished_at,
-                        type_: "PodTerminated".to_string(),
-                        message: "Pod has been terminated".to_string(),
+                        type_: "CrashLoopBackOff".to_string(),
+                        message: format!(
+                            "Pod exited with code {}, next restart at {}",
+                            terminated.exit_code,
+                            next_retry.to_rfc3339(),
+                        ),
                         observed_generation: self.meta().generation,
-                        reason: "PodTerminate".to_string(),
+                        reason: "PodCrashLoopBackOff".to_string(),
                         status: "False".to_string(),
                     });
+                    // Wake up exactly when the next restart is due.
+                    requeue_after = remaining;
                 }
             }
         }
@@ -105,6 +192,9 @@ impl SinkWebhook {
             )
             .await?;
 
+        // Expose the pod's /status server cluster-wide via a Service.
+        self.apply_status_service(&ctx, &ns, &name).await?;
+
         let pod_scheduled_condition = Condition {
             last_transition_time: pod
                 .meta()
@@ -124,15 +214,15 @@ impl SinkWebhook {
             "Running".to_string()
         };
 
+        ctx.metrics.record_restart(restart_increment as u32);
+        ctx.metrics.set_phase(&ns, &name, &phase);
+
         let mut conditions = vec![pod_scheduled_condition];
         if let Some(condition) = error_condition {
             conditions.push(condition);
         }
 
-        let restart_count = self
-            .status
-            .as_ref()
-            .map(|status| status.common.restart_count.unwrap_or_default() + restart_increment);
+        let restart_count = Some(current_restart_count + restart_increment);
 
         let status = json!({
             "status": SinkWebhookStatus {
@@ -150,7 +240,137 @@ impl SinkWebhook {
             .patch_status(&name, &PatchParams::default(), &Patch::Merge(&status))
             .await?;
 
-        Ok(Action::requeue(Duration::from_secs(10)))
+        Ok(Action::requeue(requeue_after))
+    }
+
+    /// Reconcile a sink that runs as a `batch/v1` Job.
+    ///
+    /// The controller owns the Job and derives the sink phase from its
+    /// `Complete`/`Failed` status conditions (see [`job::job_phase`]), leaving
+    /// crash retries and finished-object cleanup to Kubernetes.
+    #[instrument(skip_all)]
+    async fn reconcile_job(&self, ctx: Arc<Context>) -> Result<Action, Error> {
+        use api::batch::v1::Job;
+
+        let ns = self.namespace().expect("webhook is namespaced");
+        let name = self.name_any();
+
+        let jobs: Api<Job> = Api::namespaced(ctx.client.clone(), &ns);
+        let webhooks: Api<SinkWebhook> = Api::namespaced(ctx.client.clone(), &ns);
+
+        let settings = JobSettings::default();
+
+        // A finished Job may have been garbage-collected by its TTL. Re-applying
+        // the manifest would re-run a sink that already Succeeded/Failed, so only
+        // (re)create the Job while it is still running or has yet to exist. Once
+        // the status records a terminal phase we leave it alone.
+        let stored_phase = self
+            .status
+            .as_ref()
+            .and_then(|status| status.common.phase.clone());
+        let terminal = stored_phase
+            .as_deref()
+            .map(|phase| matches!(phase, "Succeeded" | "Error"))
+            .unwrap_or(false);
+
+        let job = match jobs.get_opt(&name).await? {
+            Some(job) if terminal => job,
+            None if terminal => {
+                // Terminal phase but the Job is gone (TTL GC): keep the last
+                // status and stop recreating it.
+                self.apply_status_service(&ctx, &ns, &name).await?;
+                if let Some(phase) = stored_phase.as_deref() {
+                    ctx.metrics.set_phase(&ns, &name, phase);
+                }
+                return Ok(Action::await_change());
+            }
+            _ => {
+                let metadata = self.object_metadata(&ctx);
+                let pod_spec = self.pod_spec(&ctx);
+                let manifest = job::job_manifest(metadata, pod_spec, &settings);
+                jobs.patch(
+                    &name,
+                    &PatchParams::apply("sinkwebhook"),
+                    &Patch::Apply(manifest),
+                )
+                .await?
+            }
+        };
+
+        // Expose the Job pod's /status server cluster-wide via a Service.
+        self.apply_status_service(&ctx, &ns, &name).await?;
+
+        let last_transition_time = job
+            .status
+            .as_ref()
+            .and_then(|status| status.completion_time.clone())
+            .or_else(|| job.meta().creation_timestamp.clone())
+            .unwrap_or(meta::v1::Time(DateTime::<Utc>::MIN_UTC));
+
+        let (phase, condition) = match job::job_phase(&job) {
+            JobPhase::Succeeded => (
+                "Succeeded",
+                Condition {
+                    last_transition_time,
+                    type_: "Completed".to_string(),
+                    message: "Job completed successfully".to_string(),
+                    observed_generation: self.meta().generation,
+                    reason: "JobSucceeded".to_string(),
+                    status: "True".to_string(),
+                },
+            ),
+            JobPhase::Failed => (
+                "Error",
+                Condition {
+                    last_transition_time,
+                    type_: "Failed".to_string(),
+                    message: "Job exceeded its backoff limit".to_string(),
+                    observed_generation: self.meta().generation,
+                    reason: "JobFailed".to_string(),
+                    status: "False".to_string(),
+                },
+            ),
+            JobPhase::Running => (
+                "Running",
+                Condition {
+                    last_transition_time,
+                    type_: "JobScheduled".to_string(),
+                    message: "Job has been scheduled".to_string(),
+                    observed_generation: self.meta().generation,
+                    reason: "JobScheduled".to_string(),
+                    status: "True".to_string(),
+                },
+            ),
+        };
+
+        // The Job tracks retries itself, so surface its failure count as the
+        // restart count rather than incrementing one by hand.
+        let restart_count = job.status.as_ref().and_then(|status| status.failed);
+
+        let status = json!({
+            "status": SinkWebhookStatus {
+                common: CommonStatus {
+                    pod_created: job.meta().creation_timestamp.clone(),
+                    instance_name: job.meta().name.clone(),
+                    phase: Some(phase.to_string()),
+                    conditions: Some(vec![condition]),
+                    restart_count,
+                }
+            }
+        });
+
+        webhooks
+            .patch_status(&name, &PatchParams::default(), &Patch::Merge(&status))
+            .await?;
+
+        ctx.metrics.set_phase(&ns, &name, phase);
+
+        // A finished Job needs no further reconciles; only wake on change.
+        if matches!(phase, "Succeeded" | "Error") {
+            return Ok(Action::await_change());
+        }
+
+        Ok(Action::requeue(ctx.configuration.timing.requeue_interval))
     }
 
     #[instrument(skip_all)]
@@ -159,24 +379,96 @@ impl SinkWebhook {
 
         let ns = self.namespace().expect("webhook is namespaced");
         let name = self.name_any();
+
+        // Drop the sink's metric series so deleted sinks stop firing alerts.
+        ctx.metrics.clear_phase(&ns, &name);
+
+        if matches!(self.spec.common.backend, ExecutionBackend::Job) {
+            use api::batch::v1::Job;
+            let jobs: Api<Job> = Api::namespaced(ctx.client.clone(), &ns);
+            if jobs.get_opt(&name).await?.is_some() {
+                // Propagate deletion so the Job's pods are cleaned up too.
+                let params = DeleteParams::default().background();
+                jobs.delete(&name, &params).await?;
+            }
+            return Ok(Action::requeue(ctx.configuration.timing.requeue_interval));
+        }
+
         let pods: Api<Pod> = Api::namespaced(ctx.client.clone(), &ns);
 
         if let Some(_existing) = pods.get_opt(&name).await? {
             pods.delete(&name, &DeleteParams::default()).await?;
         }
 
-        Ok(Action::requeue(Duration::from_secs(10)))
+        Ok(Action::requeue(ctx.configuration.timing.requeue_interval))
     }
 
     fn object_metadata(&self, _ctx: &Arc<Context>) -> meta::v1::ObjectMeta {
         use meta::v1::ObjectMeta;
+        use std::collections::BTreeMap;
+
+        let mut labels = BTreeMap::new();
+        if let Some(name) = self.metadata.name.clone() {
+            labels.insert("apibara.com/sink".to_string(), name);
+        }
 
         ObjectMeta {
             name: self.metadata.name.clone(),
+            labels: Some(labels),
             ..ObjectMeta::default()
         }
     }
 
+    /// Server-side apply the status `Service` so both the pod and Job backends
+    /// expose `/status` cluster-wide consistently.
+    async fn apply_status_service(
+        &self,
+        ctx: &Arc<Context>,
+        ns: &str,
+        name: &str,
+    ) -> Result<(), Error> {
+        let services: Api<api::core::v1::Service> = Api::namespaced(ctx.client.clone(), ns);
+        services
+            .patch(
+                name,
+                &PatchParams::apply("sinkwebhook"),
+                &Patch::Apply(self.status_service(ctx)),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Build the `Service` exposing the sink's `/status` server (port 8118) so
+    /// it is reachable cluster-wide by name. Owned by the `SinkWebhook` so it is
+    /// garbage-collected with it.
+    fn status_service(&self, _ctx: &Arc<Context>) -> api::core::v1::Service {
+        use api::core::v1::{Service, ServicePort, ServiceSpec};
+        use meta::v1::ObjectMeta;
+        use std::collections::BTreeMap;
+
+        let name = self.name_any();
+        let selector = BTreeMap::from([("apibara.com/sink".to_string(), name.clone())]);
+
+        Service {
+            metadata: ObjectMeta {
+                name: Some(name),
+                owner_references: self.controller_owner_ref(&()).map(|owner| vec![owner]),
+                ..ObjectMeta::default()
+            },
+            spec: Some(ServiceSpec {
+                selector: Some(selector),
+                ports: Some(vec![ServicePort {
+                    name: Some("status".to_string()),
+                    port: 8118,
+                    target_port: Some(IntOrString::Int(8118)),
+                    ..ServicePort::default()
+                }]),
+                ..ServiceSpec::default()
+            }),
+            ..Service::default()
+        }
+    }
+
     fn pod_spec(&self, ctx: &Arc<Context>) -> api::core::v1::PodSpec {
         use api::core::v1::{Container, ContainerPort, EnvVar, HTTPGetAction, PodSpec, Probe};
 
@@ -229,7 +521,9 @@ impl SinkWebhook {
             });
         }
 
-        // TODO: add headers environment variable, like METADATA
+        if let Some(headers) = self.spec.headers.as_ref() {
+            env.extend(super::headers::headers_to_env(headers));
+        }
 
         env.extend(self.spec.common.to_env_var());
 
@@ -297,9 +591,9 @@ async fn reconcile_webhook(webhook: Arc<SinkWebhook>, ctx: Arc<Context>) -> Resu
     .map_err(|err| Error::Finalizer(err.into()))
 }
 
-fn error_policy(_webhook: Arc<SinkWebhook>, error: &Error, _ctx: Arc<Context>) -> Action {
+fn error_policy(_webhook: Arc<SinkWebhook>, error: &Error, ctx: Arc<Context>) -> Action {
     warn!(error = ?error, "webhook reconcile error");
-    Action::requeue(Duration::from_secs(30))
+    Action::requeue(ctx.configuration.timing.error_backoff)
 }
 
 pub async fn start_controller(
@@ -314,10 +608,33 @@ pub async fn start_controller(
 
     info!("starting webhook sink controller");
 
+    let ctx = Arc::new(ctx);
+
+    // Reschedule sink pods off nodes that go unreachable, sharing the Context.
+    let node_watcher_ctx = ctx.clone();
+    tokio::spawn(async move {
+        if let Err(err) = super::node_watcher::start_node_watcher(node_watcher_ctx).await {
+            error!(error = ?err, "node watcher stopped");
+        }
+    });
+
+    // Serve controller metrics for Prometheus to scrape.
+    let metrics_ctx = ctx.clone();
+    let metrics_address = ctx.configuration.metrics_address;
+    tokio::spawn(async move {
+        if let Err(err) = super::metrics::start_metrics(metrics_ctx, metrics_address).await {
+            error!(error = ?err, "metrics server stopped");
+        }
+    });
+
     let pods = Api::<api::core::v1::Pod>::all(ctx.client.clone());
+    let jobs = Api::<api::batch::v1::Job>::all(ctx.client.clone());
+    let services = Api::<api::core::v1::Service>::all(ctx.client.clone());
     let controller = Controller::new(webhooks, Config::default())
         .owns(pods, Config::default())
-        .run(reconcile_webhook, error_policy, ctx.into());
+        .owns(jobs, Config::default())
+        .owns(services, Config::default())
+        .run(reconcile_webhook, error_policy, ctx);
 
     Ok(controller)
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use clap::Args;
+
+/// Timing knobs for the sink controllers.
+///
+/// Every value is parsed from a `humantime` string (e.g. `10s`, `5m`, `1h`) so
+/// deployments can tune how aggressively the controller reconciles and restarts
+/// sinks without recompiling: fast requeues in tests, slower ones in production.
+#[derive(Args, Clone, Debug)]
+pub struct TimingConfiguration {
+    /// How long to wait before re-reconciling a sink after a successful pass.
+    #[arg(
+        long = "sink-requeue-interval",
+        env = "DNA_SINK_REQUEUE_INTERVAL",
+        default_value = "10s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub requeue_interval: Duration,
+
+    /// How long to wait before retrying after a reconcile error.
+    #[arg(
+        long = "sink-error-backoff",
+        env = "DNA_SINK_ERROR_BACKOFF",
+        default_value = "30s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub error_backoff: Duration,
+
+    /// Base delay of the exponential backoff applied between pod restarts.
+    #[arg(
+        long = "sink-restart-threshold",
+        env = "DNA_SINK_RESTART_THRESHOLD",
+        default_value = "10s",
+        value_parser = humantime::parse_duration,
+    )]
+    pub restart_threshold: Duration,
+}
+
+impl Default for TimingConfiguration {
+    fn default() -> Self {
+        Self {
+            requeue_interval: Duration::from_secs(10),
+            error_backoff: Duration::from_secs(30),
+            restart_threshold: Duration::from_secs(10),
+        }
+    }
+}